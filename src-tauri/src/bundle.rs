@@ -0,0 +1,150 @@
+//! Archival export of struck substrate files as `.tar.xz` bundles.
+//!
+//! Each input file is validated through a streaming contaminant scan
+//! before it is packed, so a contaminated substrate can't silently end up
+//! in a distribution archive unless the caller explicitly opts in with
+//! `force`. Files are scanned and packed in bounded-memory chunks, the
+//! same way `verify_substrate` streams large substrates, rather than
+//! being read whole into memory.
+
+use crate::{find_contaminants_at, Contaminant, STREAM_CHUNK_SIZE};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use xz2::stream::{Check, Filters, LzmaOptions, Stream};
+use xz2::write::XzEncoder;
+
+/// Default xz dictionary/window size in megabytes
+const DEFAULT_WINDOW_MB: u8 = 8;
+
+/// Largest xz dictionary/window size we'll configure
+const MAX_WINDOW_MB: u8 = 64;
+
+/// One entry in the bundle's manifest, recording what was packed and
+/// whether it passed contamination checks.
+#[derive(Serialize)]
+struct BundleManifestEntry {
+    path: String,
+    byte_count: usize,
+    clean: bool,
+    contaminants: Vec<Contaminant>,
+}
+
+/// Scan a file for contaminants in bounded-memory chunks, without loading
+/// the whole file into memory.
+fn scan_contaminants_streaming(path: &str) -> Result<(Vec<Contaminant>, usize), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut reader = BufReader::with_capacity(STREAM_CHUNK_SIZE, file);
+    let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+    let mut contaminants = Vec::new();
+    let mut offset = 0usize;
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if n == 0 {
+            break;
+        }
+        contaminants.extend(find_contaminants_at(&buf[..n], offset));
+        offset += n;
+    }
+
+    Ok((contaminants, offset))
+}
+
+fn xz_encoder(out: File, window_mb: u8) -> Result<XzEncoder<File>, String> {
+    let dict_size = (window_mb.clamp(1, MAX_WINDOW_MB) as u32) * 1024 * 1024;
+
+    let mut options = LzmaOptions::new_preset(6)
+        .map_err(|e| format!("Failed to configure xz encoder: {}", e))?;
+    options.dict_size(dict_size);
+
+    let mut filters = Filters::new();
+    filters.lzma2(&options);
+
+    let stream = Stream::new_stream_encoder(&filters, Check::Crc64)
+        .map_err(|e| format!("Failed to configure xz encoder: {}", e))?;
+
+    Ok(XzEncoder::new_stream(out, stream))
+}
+
+/// Pack one or more struck substrate files into a `.tar.xz` archive at `out`.
+///
+/// `window_mb` sets the xz dictionary/window size (default 8 MB, capped at
+/// 64 MB); a larger window shrinks the archive at the cost of more memory
+/// during compression. Every input is checked with a streaming contaminant
+/// scan first; a contaminated file is refused unless `force` is set. A
+/// `manifest.json` entry is written into the archive per file, recording
+/// its byte count and clean/contaminated status.
+#[tauri::command]
+pub fn export_substrate_bundle(
+    paths: Vec<String>,
+    out: String,
+    window_mb: Option<u8>,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let window_mb = window_mb.unwrap_or(DEFAULT_WINDOW_MB);
+    let force = force.unwrap_or(false);
+
+    let mut manifest = Vec::with_capacity(paths.len());
+
+    for path in &paths {
+        let (contaminants, byte_count) = scan_contaminants_streaming(path)?;
+        let clean = contaminants.is_empty();
+
+        if !clean && !force {
+            return Err(format!(
+                "{} contains {} contaminant(s); pass force to bundle it anyway",
+                path,
+                contaminants.len()
+            ));
+        }
+
+        manifest.push(BundleManifestEntry {
+            path: path.clone(),
+            byte_count,
+            clean,
+            contaminants,
+        });
+    }
+
+    let out_file = File::create(&out).map_err(|e| format!("Failed to create {}: {}", out, e))?;
+    let xz = xz_encoder(out_file, window_mb)?;
+    let mut builder = tar::Builder::new(xz);
+
+    for path in &paths {
+        let metadata = fs::metadata(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let mut file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let name = std::path::Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path);
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(metadata.len());
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, &mut file)
+            .map_err(|e| format!("Failed to pack {}: {}", path, e))?;
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to build manifest: {}", e))?;
+    let mut manifest_header = tar::Header::new_gnu();
+    manifest_header.set_size(manifest_json.len() as u64);
+    manifest_header.set_mode(0o644);
+    manifest_header.set_cksum();
+    builder
+        .append_data(&mut manifest_header, "manifest.json", &manifest_json[..])
+        .map_err(|e| format!("Failed to pack manifest: {}", e))?;
+
+    let xz = builder
+        .into_inner()
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+    xz.finish()
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! Append-only provenance journal for strike operations.
+//!
+//! Every `execute_forth_strike` invocation appends one line of JSON to
+//! `.dotmatrix/journal.jsonl` -- completed, or failed at any step, from
+//! byte validation through spawning gforth. `gforth_exit_code` is `None`
+//! when the kernel was never reached. Entries are never rewritten in
+//! place, only appended, so the journal is an audit trail: a strike can be
+//! traced back to the bytes it struck and
+//! the outcome of that attempt. `replay_strike` re-derives that state from
+//! the substrate file currently on disk and never invokes gforth, so a
+//! replay can never mutate anything.
+
+use crate::{find_contaminants, find_contaminants_at, Contaminant, STREAM_CHUNK_SIZE};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const JOURNAL_PATH: &str = ".dotmatrix/journal.jsonl";
+
+/// One append-only record of a completed or failed strike.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JournalEntry {
+    pub timestamp: u64,
+    pub path: String,
+    pub byte_count: usize,
+    pub sha256_of_bytes: String,
+    pub contaminants: Vec<Contaminant>,
+    pub gforth_exit_code: Option<i32>,
+    pub forth_args: Vec<String>,
+}
+
+/// Result of re-verifying a journal entry against the file on disk today.
+#[derive(Serialize)]
+pub struct ReplayResult {
+    pub entry_id: usize,
+    pub hash_matches: bool,
+    pub still_clean: bool,
+    pub contaminants: Vec<Contaminant>,
+    pub error: Option<String>,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Hash and scan a substrate file for contaminants in bounded-memory
+/// chunks, the same way `verify_substrate` streams large files, instead
+/// of loading the whole file into memory.
+fn scan_file_streaming(path: &str) -> std::io::Result<(String, Vec<Contaminant>, usize)> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::with_capacity(STREAM_CHUNK_SIZE, file);
+    let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+    let mut hasher = Sha256::new();
+    let mut contaminants = Vec::new();
+    let mut offset = 0usize;
+
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        contaminants.extend(find_contaminants_at(&buf[..n], offset));
+        offset += n;
+    }
+
+    let hash = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+    Ok((hash, contaminants, offset))
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Append one entry to the journal, creating `.dotmatrix/` if needed.
+/// This only ever opens the file in append mode: existing lines are
+/// never touched.
+fn append_entry(entry: &JournalEntry) -> std::io::Result<()> {
+    if let Some(parent) = Path::new(JOURNAL_PATH).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut f = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(JOURNAL_PATH)?;
+    let line = serde_json::to_string(entry)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    writeln!(f, "{}", line)
+}
+
+/// Record a strike attempt, whether or not it reached the Gforth kernel.
+/// `gforth_exit_code` is `None` when gforth never returned an exit code;
+/// `forth_args` is empty when the attempt failed before the gforth command
+/// line was built (a failed spawn still records it). Provenance is best-effort: a
+/// journal write failure must never fail the strike itself, so errors
+/// here are swallowed.
+pub fn record_strike(path: &str, bytes: &[u8], gforth_exit_code: Option<i32>, forth_args: Vec<String>) {
+    let entry = JournalEntry {
+        timestamp: now_unix(),
+        path: path.to_string(),
+        byte_count: bytes.len(),
+        sha256_of_bytes: sha256_hex(bytes),
+        contaminants: find_contaminants(bytes),
+        gforth_exit_code,
+        forth_args,
+    };
+    let _ = append_entry(&entry);
+}
+
+fn read_entries() -> Result<Vec<JournalEntry>, String> {
+    let path = Path::new(JOURNAL_PATH);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let f = File::open(path).map_err(|e| format!("Failed to open {}: {}", JOURNAL_PATH, e))?;
+    BufReader::new(f)
+        .lines()
+        .filter(|l| l.as_ref().map(|s| !s.trim().is_empty()).unwrap_or(true))
+        .map(|l| {
+            let line = l.map_err(|e| format!("Failed to read {}: {}", JOURNAL_PATH, e))?;
+            serde_json::from_str(&line).map_err(|e| format!("Malformed journal entry: {}", e))
+        })
+        .collect()
+}
+
+/// List all recorded journal entries, oldest first. The index of an entry
+/// in this list is its `entry_id` for `replay_strike`.
+#[tauri::command]
+pub fn journal_list() -> Result<Vec<JournalEntry>, String> {
+    read_entries()
+}
+
+/// Re-read the substrate file referenced by a journal entry and check
+/// whether it still matches the recorded hash and contamination state.
+/// Never invokes gforth: this only re-hashes and re-scans the bytes
+/// currently on disk, streamed in bounded-memory chunks so replaying a
+/// multi-gigabyte substrate doesn't load it whole.
+#[tauri::command]
+pub fn replay_strike(entry_id: usize) -> Result<ReplayResult, String> {
+    let entries = read_entries()?;
+    let entry = entries
+        .get(entry_id)
+        .ok_or_else(|| format!("No journal entry with id {}", entry_id))?;
+
+    match scan_file_streaming(&entry.path) {
+        Ok((hash, contaminants, _byte_count)) => Ok(ReplayResult {
+            entry_id,
+            hash_matches: hash == entry.sha256_of_bytes,
+            still_clean: contaminants.is_empty(),
+            contaminants,
+            error: None,
+        }),
+        Err(e) => Ok(ReplayResult {
+            entry_id,
+            hash_matches: false,
+            still_clean: false,
+            contaminants: Vec::new(),
+            error: Some(format!("Failed to read {}: {}", entry.path, e)),
+        }),
+    }
+}
@@ -6,11 +6,14 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
-use std::io::Write;
+use std::io::{BufReader, Read, Write};
 use std::path::Path;
 use std::process::Command;
 use thiserror::Error;
 
+mod bundle;
+mod journal;
+
 /// Constraints from Nickel contract (config/meta.ncl)
 mod constraints {
     pub const MAX_BYTE: u8 = 127;
@@ -31,8 +34,16 @@ pub enum StrikeError {
         description: String,
     },
 
-    #[error("File operation failed: {0}")]
-    FileError(String),
+    /// A filesystem step failed. `kind` is the `io::ErrorKind` discriminant
+    /// (e.g. "NotFound", "PermissionDenied") so the ReScript UI can branch
+    /// on it instead of pattern-matching the message text.
+    #[error("{operation} {path}: {kind}")]
+    FileError {
+        operation: &'static str,
+        path: String,
+        #[serde(serialize_with = "serialize_error_kind")]
+        kind: std::io::ErrorKind,
+    },
 
     #[error("Forth kernel execution failed: {0}")]
     ForthError(String),
@@ -41,14 +52,25 @@ pub enum StrikeError {
     GforthNotFound,
 }
 
-impl From<std::io::Error> for StrikeError {
-    fn from(e: std::io::Error) -> Self {
-        StrikeError::FileError(e.to_string())
+fn serialize_error_kind<S>(kind: &std::io::ErrorKind, s: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    s.serialize_str(&format!("{:?}", kind))
+}
+
+/// Build a `StrikeError::FileError` tying an I/O failure to the filesystem
+/// step and path that caused it.
+fn file_error(operation: &'static str, path: impl Into<String>, e: &std::io::Error) -> StrikeError {
+    StrikeError::FileError {
+        operation,
+        path: path.into(),
+        kind: e.kind(),
     }
 }
 
 /// Result of contamination check
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Contaminant {
     position: usize,
     value: u8,
@@ -78,6 +100,7 @@ pub struct VerifyResult {
     contaminants: Vec<Contaminant>,
     hexdump: String,
     size: usize,
+    truncated: bool,
 }
 
 /// Validate a single byte against constraints
@@ -105,35 +128,71 @@ fn validate_byte(byte: u8, position: usize) -> Result<(), StrikeError> {
     Ok(())
 }
 
+/// Classify a single byte at a given absolute position, if it's a contaminant
+fn classify_byte(position: usize, b: u8) -> Option<Contaminant> {
+    if b > constraints::MAX_BYTE {
+        Some(Contaminant {
+            position,
+            value: b,
+            description: format!("Non-ASCII (0x{:02X} > 127)", b),
+        })
+    } else if b == constraints::FORBIDDEN_NBSP {
+        Some(Contaminant {
+            position,
+            value: b,
+            description: "NBSP (Non-Breaking Space)".into(),
+        })
+    } else if b == constraints::FORBIDDEN_UTF8 {
+        Some(Contaminant {
+            position,
+            value: b,
+            description: "UTF-8 continuation marker".into(),
+        })
+    } else {
+        None
+    }
+}
+
 /// Find all contaminants in a byte sequence (non-failing)
-fn find_contaminants(bytes: &[u8]) -> Vec<Contaminant> {
+pub(crate) fn find_contaminants(bytes: &[u8]) -> Vec<Contaminant> {
+    bytes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &b)| classify_byte(i, b))
+        .collect()
+}
+
+/// Find all contaminants in one chunk of a larger stream, given the chunk's
+/// offset within the full file
+pub(crate) fn find_contaminants_at(bytes: &[u8], offset: usize) -> Vec<Contaminant> {
     bytes
         .iter()
         .enumerate()
-        .filter_map(|(i, &b)| {
-            if b > constraints::MAX_BYTE {
-                Some(Contaminant {
-                    position: i,
-                    value: b,
-                    description: format!("Non-ASCII (0x{:02X} > 127)", b),
-                })
-            } else if b == constraints::FORBIDDEN_NBSP {
-                Some(Contaminant {
-                    position: i,
-                    value: b,
-                    description: "NBSP (Non-Breaking Space)".into(),
-                })
-            } else if b == constraints::FORBIDDEN_UTF8 {
-                Some(Contaminant {
-                    position: i,
-                    value: b,
-                    description: "UTF-8 continuation marker".into(),
-                })
+        .filter_map(|(i, &b)| classify_byte(offset + i, b))
+        .collect()
+}
+
+/// Format one 16-byte row of a hexdump at the given file offset
+fn format_hexdump_row(addr: usize, chunk: &[u8]) -> String {
+    let hex: String = chunk
+        .iter()
+        .enumerate()
+        .map(|(j, b)| {
+            if j == 8 {
+                format!(" {:02x}", b)
             } else {
-                None
+                format!("{:02x}", b)
             }
         })
-        .collect()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let ascii: String = chunk
+        .iter()
+        .map(|&b| if b >= 32 && b < 127 { b as char } else { '.' })
+        .collect();
+
+    format!("{:08x}  {:48}  |{}|", addr, hex, ascii)
 }
 
 /// Generate hexdump-style output
@@ -141,33 +200,7 @@ fn bytes_to_hexdump(bytes: &[u8]) -> String {
     bytes
         .chunks(16)
         .enumerate()
-        .map(|(i, chunk)| {
-            let hex: String = chunk
-                .iter()
-                .enumerate()
-                .map(|(j, b)| {
-                    if j == 8 {
-                        format!(" {:02x}", b)
-                    } else {
-                        format!("{:02x}", b)
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
-
-            let ascii: String = chunk
-                .iter()
-                .map(|&b| {
-                    if b >= 32 && b < 127 {
-                        b as char
-                    } else {
-                        '.'
-                    }
-                })
-                .collect();
-
-            format!("{:08x}  {:48}  |{}|", i * 16, hex, ascii)
-        })
+        .map(|(i, chunk)| format_hexdump_row(i * 16, chunk))
         .collect::<Vec<_>>()
         .join("\n")
 }
@@ -194,81 +227,168 @@ fn preview_forth_strike(bytes: Vec<u8>) -> PreviewResult {
     }
 }
 
-/// Execute a strike via the Forth kernel
+/// Execute a strike via the Forth kernel. Every attempt is journaled, even
+/// one that fails before gforth is ever spawned, so `gforth_exit_code` is
+/// `None` whenever the kernel step wasn't reached.
 #[tauri::command]
-fn execute_forth_strike(bytes: Vec<u8>, path: String) -> Result<(), String> {
-    // Validate all bytes first
-    for (i, &b) in bytes.iter().enumerate() {
-        validate_byte(b, i).map_err(|e| e.to_string())?;
-    }
+fn execute_forth_strike(bytes: Vec<u8>, path: String) -> Result<(), StrikeError> {
+    let mut gforth_exit_code: Option<i32> = None;
+    let mut forth_args: Vec<String> = Vec::new();
 
-    // Check gforth availability
-    if !check_gforth() {
-        return Err(StrikeError::GforthNotFound.to_string());
-    }
+    let result = (|| -> Result<(), StrikeError> {
+        // Validate all bytes first
+        for (i, &b) in bytes.iter().enumerate() {
+            validate_byte(b, i)?;
+        }
 
-    // Ensure kernel directory exists
-    let kernel_dir = Path::new("kernel");
-    if !kernel_dir.exists() {
-        return Err(StrikeError::FileError("kernel/ directory not found".into()).to_string());
-    }
+        // Check gforth availability
+        if !check_gforth() {
+            return Err(StrikeError::GforthNotFound);
+        }
 
-    // Write data to temporary Forth source
-    let data_path = kernel_dir.join("data.fth");
-    let mut f = File::create(&data_path).map_err(|e| StrikeError::from(e).to_string())?;
+        // Ensure kernel directory exists
+        let kernel_dir = Path::new("kernel");
+        if !kernel_dir.exists() {
+            return Err(StrikeError::FileError {
+                operation: "locate",
+                path: "kernel".into(),
+                kind: std::io::ErrorKind::NotFound,
+            });
+        }
 
-    writeln!(f, "\\ Auto-generated strike data").map_err(|e| e.to_string())?;
-    write!(f, "CREATE STRIKE-DATA ").map_err(|e| e.to_string())?;
-    for b in &bytes {
-        write!(f, "{} , ", b).map_err(|e| e.to_string())?;
-    }
-    writeln!(f).map_err(|e| e.to_string())?;
+        // Write data to temporary Forth source
+        let data_path = kernel_dir.join("data.fth");
+        let data_path_str = data_path.display().to_string();
+        let mut f =
+            File::create(&data_path).map_err(|e| file_error("create", data_path_str.clone(), &e))?;
 
-    // Ensure dist directory exists
-    if let Some(parent) = Path::new(&path).parent() {
-        fs::create_dir_all(parent).map_err(|e| StrikeError::from(e).to_string())?;
-    }
+        writeln!(f, "\\ Auto-generated strike data")
+            .map_err(|e| file_error("write", data_path_str.clone(), &e))?;
+        write!(f, "CREATE STRIKE-DATA ").map_err(|e| file_error("write", data_path_str.clone(), &e))?;
+        for b in &bytes {
+            write!(f, "{} , ", b).map_err(|e| file_error("write", data_path_str.clone(), &e))?;
+        }
+        writeln!(f).map_err(|e| file_error("write", data_path_str.clone(), &e))?;
 
-    // Invoke Gforth Kernel
-    let status = Command::new("gforth")
-        .args([
-            "kernel/striker.fth",
-            "kernel/data.fth",
-            "-e",
-            &format!(
+        // Ensure dist directory exists
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent).map_err(|e| file_error("create", parent.display().to_string(), &e))?;
+        }
+
+        // Invoke Gforth Kernel
+        forth_args = vec![
+            "kernel/striker.fth".into(),
+            "kernel/data.fth".into(),
+            "-e".into(),
+            format!(
                 "s\" {}\" strike-init STRIKE-DATA {} strike-sequence strike-close bye",
                 path,
                 bytes.len()
             ),
-        ])
-        .status()
-        .map_err(|e| StrikeError::ForthError(e.to_string()).to_string())?;
+        ];
 
-    if status.success() {
-        Ok(())
-    } else {
-        Err(StrikeError::ForthError("Forth kernel returned non-zero exit code".into()).to_string())
-    }
+        let status = Command::new("gforth")
+            .args(&forth_args)
+            .status()
+            .map_err(|e| file_error("spawn", "gforth", &e))?;
+
+        gforth_exit_code = status.code();
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(StrikeError::ForthError("Forth kernel returned non-zero exit code".into()))
+        }
+    })();
+
+    journal::record_strike(&path, &bytes, gforth_exit_code, forth_args);
+
+    result
 }
 
-/// Read and verify a substrate file
+/// Buffer size used to stream substrate files in bounded-memory chunks
+pub(crate) const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Default cap on the number of hexdump rows returned to the UI
+const DEFAULT_MAX_HEXDUMP_ROWS: usize = 4096;
+
+/// Read and verify a substrate file, scanning it in fixed-size chunks so
+/// memory use stays bounded regardless of file size. The hexdump is capped
+/// at `max_hexdump_rows` rows (each row covers 16 bytes); contamination
+/// scanning always covers the entire file regardless of that cap.
 #[tauri::command]
-fn verify_substrate(path: String) -> Result<VerifyResult, String> {
-    let bytes = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
-    let contaminants = find_contaminants(&bytes);
+fn verify_substrate(path: String, max_hexdump_rows: Option<usize>) -> Result<VerifyResult, String> {
+    let max_hexdump_rows = max_hexdump_rows.unwrap_or(DEFAULT_MAX_HEXDUMP_ROWS);
+
+    let file = File::open(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut reader = BufReader::with_capacity(STREAM_CHUNK_SIZE, file);
+    let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+
+    let mut offset = 0usize;
+    let mut contaminants = Vec::new();
+    let mut hexdump_rows: Vec<String> = Vec::new();
+    let mut truncated = false;
+    let mut row_addr = 0usize;
+    let mut pending: Vec<u8> = Vec::new();
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if n == 0 {
+            break;
+        }
+        let chunk = &buf[..n];
+        contaminants.extend(find_contaminants_at(chunk, offset));
+        offset += n;
+
+        pending.extend_from_slice(chunk);
+        let mut consumed = 0;
+        for row in pending.chunks(16) {
+            if row.len() < 16 {
+                break;
+            }
+            if hexdump_rows.len() < max_hexdump_rows {
+                hexdump_rows.push(format_hexdump_row(row_addr, row));
+            } else {
+                truncated = true;
+            }
+            row_addr += 16;
+            consumed += 16;
+        }
+        pending.drain(..consumed);
+    }
+
+    if !pending.is_empty() {
+        if hexdump_rows.len() < max_hexdump_rows {
+            hexdump_rows.push(format_hexdump_row(row_addr, &pending));
+        } else {
+            truncated = true;
+        }
+    }
+
+    let mut hexdump = hexdump_rows.join("\n");
+    if truncated {
+        hexdump.push_str(&format!(
+            "\n... (truncated, showing {} of {} rows)",
+            hexdump_rows.len(),
+            (offset + 15) / 16
+        ));
+    }
 
     Ok(VerifyResult {
         clean: contaminants.is_empty(),
         contaminants,
-        hexdump: bytes_to_hexdump(&bytes),
-        size: bytes.len(),
+        hexdump,
+        size: offset,
+        truncated,
     })
 }
 
 /// Read substrate as hex (for display)
 #[tauri::command]
-fn read_substrate_hex(path: String) -> Result<VerifyResult, String> {
-    verify_substrate(path)
+fn read_substrate_hex(path: String, max_hexdump_rows: Option<usize>) -> Result<VerifyResult, String> {
+    verify_substrate(path, max_hexdump_rows)
 }
 
 fn main() {
@@ -282,6 +402,9 @@ fn main() {
             execute_forth_strike,
             verify_substrate,
             read_substrate_hex,
+            journal::journal_list,
+            journal::replay_strike,
+            bundle::export_substrate_bundle,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");